@@ -1,59 +1,339 @@
 mod scryfall {
-	use const_format::concatcp;
-
-	const BEGIN_DOCUMENT_HTML: &'static str =
-		concatcp!("<!DOCTYPE html>", "<html>", HEAD_HTML, "<body>", "<ul>");
-
-	const END_DOCUMENT_HTML: &'static str =
-		concatcp!("</ul>", "</body>", "</html>",);
-
-	const HEAD_HTML: &'static str = concatcp!(
-		"<head>",
-		PRINTER_STYLE_HTML,
-		"<title>Scryfall Proxy</title>",
-		"</head>",
-	);
-
-	const PRINTER_STYLE_HTML: &'static str = indoc::indoc! {"
-		<style>
-			body {
-				margin: 0;
-				padding: 0;
-				width: 210mm;
-			}
-			ul {
-				align-content: flex-start;
-				display: flex;
-				flex-wrap: wrap;
-				margin: 0;
-				padding: 0;
-				page-break-inside: avoid;
-			}
-			img {
-				height: 88mm;
-				width: 63mm;
-			}
-		</style>
-	"};
+	// Physical dimensions of a standard Magic card.
+	const CARD_WIDTH_MM: u32 = 63;
+	const CARD_HEIGHT_MM: u32 = 88;
+
+	#[derive(Clone, Copy)]
+	pub enum Paper {
+		A4,
+		Letter,
+	}
+
+	impl Paper {
+		fn width_mm(&self) -> u32 {
+			match self {
+				Paper::A4 => 210,
+				Paper::Letter => 216,
+			}
+		}
+
+		fn height_mm(&self) -> u32 {
+			match self {
+				Paper::A4 => 297,
+				Paper::Letter => 279,
+			}
+		}
+
+		fn parse_from(name: &str) -> Option<Paper> {
+			match name {
+				"a4" => Some(Paper::A4),
+				"letter" => Some(Paper::Letter),
+				_ => None,
+			}
+		}
+	}
+
+	// Print-grid layout: a `cards_per_row` × `rows_per_page` sheet of cards on
+	// the chosen paper size.
+	pub struct GridConfig {
+		pub cards_per_row: usize,
+		pub rows_per_page: usize,
+		pub paper: Paper,
+	}
+
+	impl GridConfig {
+		fn page_size(&self) -> usize {
+			(self.cards_per_row * self.rows_per_page).max(1)
+		}
+	}
+
+	impl Default for GridConfig {
+		fn default() -> GridConfig {
+			GridConfig {
+				cards_per_row: 3,
+				rows_per_page: 3,
+				paper: Paper::A4,
+			}
+		}
+	}
+
+	// Build the print stylesheet for the requested grid. The `ul` width forces
+	// `cards_per_row` cards per row, and `page-break-after` starts a fresh
+	// sheet after each page.
+	fn printer_style(grid: &GridConfig) -> String {
+		// Scale the cards down (never up) so that `cards_per_row` columns and
+		// `rows_per_page` rows both fit the paper, preserving the 63×88mm
+		// aspect ratio. Without this a grid like 4 rows on A4 would overflow
+		// the sheet silently.
+		let paper_width = grid.paper.width_mm() as f64;
+		let paper_height = grid.paper.height_mm() as f64;
+		let grid_width = grid.cards_per_row.max(1) as f64 * CARD_WIDTH_MM as f64;
+		let grid_height =
+			grid.rows_per_page.max(1) as f64 * CARD_HEIGHT_MM as f64;
+		let scale = (paper_width / grid_width)
+			.min(paper_height / grid_height)
+			.min(1.0);
+		let card_width = CARD_WIDTH_MM as f64 * scale;
+		let card_height = CARD_HEIGHT_MM as f64 * scale;
+
+		format!(
+			indoc::indoc! {"
+				<style>
+					body {{
+						margin: 0;
+						padding: 0;
+						width: {paper_width}mm;
+					}}
+					ul {{
+						align-content: flex-start;
+						display: flex;
+						flex-wrap: wrap;
+						margin: 0;
+						padding: 0;
+						page-break-inside: avoid;
+						page-break-after: always;
+						width: {row_width:.3}mm;
+					}}
+					ul:last-child {{
+						page-break-after: auto;
+					}}
+					img {{
+						height: {card_height:.3}mm;
+						width: {card_width:.3}mm;
+					}}
+				</style>
+			"},
+			paper_width = grid.paper.width_mm(),
+			row_width = grid.cards_per_row.max(1) as f64 * card_width,
+			card_height = card_height,
+			card_width = card_width,
+		)
+	}
+
+	fn document(grid: &GridConfig, body: &str) -> String {
+		format!(
+			concat!(
+				"<!DOCTYPE html><html><head>{}",
+				"<title>Scryfall Proxy</title></head><body><ul>{}</ul>",
+				"</body></html>",
+			),
+			printer_style(grid),
+			body,
+		)
+	}
 
 	pub enum RuntimeError {
 		InvalidCardCountNumberError,
 		MalformedLineError,
 		ParseJsonError,
 		ParseStdinError,
+		CacheIoError,
+		CardsNotFoundError(Vec<String>),
+		LineParseError(usize, String),
+		RequestTimeoutError,
+		TooManyRequestsError,
 		WebRequestBodyParseError,
 		WebRequestError,
+		WebRequestNotFound,
+	}
+
+	const USER_AGENT: &'static str = "scryfall_proxy/0.1";
+
+	// Scryfall asks clients to insert 50–100 ms between requests; serialise the
+	// spacing of outgoing calls through a shared gate while still letting the
+	// requests themselves overlap.
+	struct RateLimiter {
+		last: tokio::sync::Mutex<Option<std::time::Instant>>,
+		min_delay: std::time::Duration,
+	}
+
+	impl RateLimiter {
+		fn new(min_delay: std::time::Duration) -> RateLimiter {
+			RateLimiter {
+				last: tokio::sync::Mutex::new(None),
+				min_delay,
+			}
+		}
+
+		async fn throttle(&self) {
+			let mut last = self.last.lock().await;
+			if let Some(prev) = *last {
+				let elapsed = prev.elapsed();
+				if elapsed < self.min_delay {
+					tokio::time::sleep(self.min_delay - elapsed).await;
+				}
+			}
+			*last = Some(std::time::Instant::now());
+		}
+	}
+
+	enum Resolution {
+		SetCode { code: String, set: String },
+		FuzzyName(String),
 	}
 
 	struct LineCard {
 		count: u8,
-		code: String,
-		set: String,
+		resolution: Resolution,
+	}
+
+	#[derive(Clone, Copy)]
+	pub enum ImageVariant {
+		Small,
+		Normal,
+		Large,
+		Png,
+		ArtCrop,
+		BorderCrop,
+	}
+
+	impl ImageVariant {
+		fn parse_from(name: &str) -> Option<ImageVariant> {
+			match name {
+				"small" => Some(ImageVariant::Small),
+				"normal" => Some(ImageVariant::Normal),
+				"large" => Some(ImageVariant::Large),
+				"png" => Some(ImageVariant::Png),
+				"art_crop" => Some(ImageVariant::ArtCrop),
+				"border_crop" => Some(ImageVariant::BorderCrop),
+				_ => None,
+			}
+		}
+	}
+
+	// Read-through / write-back cache of raw card JSON keyed on the resolved
+	// request URL, so repeated runs of the same decklist skip the network.
+	pub mod cache {
+		use super::RuntimeError;
+		use std::hash::{Hash, Hasher};
+
+		// Default entries are kept for a week.
+		const DEFAULT_TTL_SECS: u64 = 60 * 60 * 24 * 7;
+
+		pub struct CachePolicy {
+			pub enabled: bool,
+			pub refresh: bool,
+			pub ttl: std::time::Duration,
+		}
+
+		impl Default for CachePolicy {
+			fn default() -> CachePolicy {
+				CachePolicy {
+					enabled: true,
+					refresh: false,
+					ttl: std::time::Duration::from_secs(DEFAULT_TTL_SECS),
+				}
+			}
+		}
+
+		fn cache_dir() -> Option<std::path::PathBuf> {
+			std::env::var_os("HOME").map(|home| {
+				let mut path = std::path::PathBuf::from(home);
+				path.push(".cache");
+				path.push("scryfall_proxy");
+				path
+			})
+		}
+
+		fn entry_path(url: &str) -> Option<std::path::PathBuf> {
+			let mut hasher =
+				std::collections::hash_map::DefaultHasher::new();
+			url.hash(&mut hasher);
+			cache_dir().map(|mut dir| {
+				dir.push(format!("{:016x}.json", hasher.finish()));
+				dir
+			})
+		}
+
+		// Return a cache entry for `url` that is still within the TTL, or
+		// `None` on a miss, a stale entry, or when caching is disabled.
+		pub fn read(url: &str, policy: &CachePolicy) -> Option<String> {
+			if !policy.enabled || policy.refresh {
+				return None;
+			}
+
+			let path = entry_path(url)?;
+			let metadata = std::fs::metadata(&path).ok()?;
+			let age = metadata.modified().ok()?.elapsed().ok()?;
+			if age > policy.ttl {
+				return None;
+			}
+
+			std::fs::read_to_string(&path).ok()
+		}
+
+		// Write a successful response back to the cache, creating the cache
+		// directory on first use.
+		pub fn write(
+			url: &str,
+			json: &str,
+			policy: &CachePolicy,
+		) -> Result<(), RuntimeError> {
+			if !policy.enabled {
+				return Ok(());
+			}
+
+			let dir = match cache_dir() {
+				Some(dir) => dir,
+				None => return Ok(()),
+			};
+			std::fs::create_dir_all(&dir)
+				.map_err(|_| RuntimeError::CacheIoError)?;
+
+			match entry_path(url) {
+				Some(path) => std::fs::write(&path, json)
+					.map_err(|_| RuntimeError::CacheIoError),
+				None => Ok(()),
+			}
+		}
 	}
 
-	#[derive(Clone, serde::Deserialize)]
+	// Rendering options supplied by the caller.
+	pub struct Config {
+		pub variant: ImageVariant,
+		pub front_only: bool,
+		pub cache: cache::CachePolicy,
+		pub grid: GridConfig,
+	}
+
+	impl Default for Config {
+		fn default() -> Config {
+			Config {
+				variant: ImageVariant::Large,
+				front_only: false,
+				cache: cache::CachePolicy::default(),
+				grid: GridConfig::default(),
+			}
+		}
+	}
+
+	#[derive(Clone, serde::Deserialize, Default)]
 	struct ImageUriGroup {
+		#[serde(default)]
+		small: String,
+		#[serde(default)]
+		normal: String,
+		#[serde(default)]
 		large: String,
+		#[serde(default)]
+		png: String,
+		#[serde(default)]
+		art_crop: String,
+		#[serde(default)]
+		border_crop: String,
+	}
+
+	impl ImageUriGroup {
+		fn select(&self, variant: ImageVariant) -> &str {
+			match variant {
+				ImageVariant::Small => &self.small,
+				ImageVariant::Normal => &self.normal,
+				ImageVariant::Large => &self.large,
+				ImageVariant::Png => &self.png,
+				ImageVariant::ArtCrop => &self.art_crop,
+				ImageVariant::BorderCrop => &self.border_crop,
+			}
+		}
 	}
 
 	#[derive(serde::Deserialize, Clone)]
@@ -67,79 +347,471 @@ mod scryfall {
 	}
 
 	trait HtmlImgContent {
-		fn img_content(&self) -> String;
+		fn img_content(&self, variant: ImageVariant) -> String;
 	}
 
 	type JsonString = String;
 
+	// A Scryfall set code is a short run of ASCII alphanumerics (e.g. `2x2`).
+	fn is_set_code(token: &str) -> bool {
+		(1..=5).contains(&token.len())
+			&& token.bytes().all(|b| b.is_ascii_alphanumeric())
+	}
+
+	// Collector numbers are alphanumeric and always contain at least one digit
+	// (e.g. `117`, `117a`). This is what distinguishes a trailing set/collector
+	// pair from two words of a card name.
+	fn is_collector_number(token: &str) -> bool {
+		!token.is_empty()
+			&& token.bytes().all(|b| b.is_ascii_alphanumeric())
+			&& token.bytes().any(|b| b.is_ascii_digit())
+	}
+
+	fn url_encode(value: &str) -> String {
+		value
+			.bytes()
+			.map(|b| match b {
+				b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_'
+				| b'.' | b'~' => (b as char).to_string(),
+				_ => format!("%{:02X}", b),
+			})
+			.collect()
+	}
+
+	fn fuzzy_named_url(name: &str) -> String {
+		format!(
+			"https://api.scryfall.com/cards/named?fuzzy={}",
+			url_encode(name)
+		)
+	}
+
 	impl LineCard {
-		fn download(&self) -> Result<JsonString, RuntimeError> {
-			let response = match reqwest::blocking::get(self.to_url()) {
-				Ok(data) => data,
-				Err(_) => return Err(RuntimeError::WebRequestError),
-			};
+		async fn request(
+			&self,
+			client: &reqwest::Client,
+			limiter: &RateLimiter,
+			url: &str,
+		) -> Result<JsonString, RuntimeError> {
+			limiter.throttle().await;
 
-			match response.text() {
-				Ok(s) => Ok(s),
-				Err(_) => Err(RuntimeError::WebRequestBodyParseError),
+			let response = client
+				.get(url)
+				.header(reqwest::header::USER_AGENT, USER_AGENT)
+				.header(reqwest::header::ACCEPT, "application/json")
+				.send()
+				.await
+				.map_err(|e| {
+					if e.is_timeout() {
+						RuntimeError::RequestTimeoutError
+					} else {
+						RuntimeError::WebRequestError
+					}
+				})?;
+
+			match response.status() {
+				reqwest::StatusCode::TOO_MANY_REQUESTS => {
+					return Err(RuntimeError::TooManyRequestsError)
+				}
+				reqwest::StatusCode::NOT_FOUND => {
+					return Err(RuntimeError::WebRequestNotFound)
+				}
+				status if !status.is_success() => {
+					return Err(RuntimeError::WebRequestError)
+				}
+				_ => {}
 			}
-		}
 
-		fn parse_from(line_str: &str) -> Result<LineCard, RuntimeError> {
-			let mut token_group = line_str.split(" ");
-			let _count: u8 = match token_group.next() {
-				Some(s) => match s.parse::<u8>() {
-					Ok(n) => n,
+			response
+				.text()
+				.await
+				.map_err(|_| RuntimeError::WebRequestBodyParseError)
+		}
 
-					Err(_) => {
-						return Err(RuntimeError::InvalidCardCountNumberError)
+		async fn download(
+			&self,
+			client: &reqwest::Client,
+			limiter: &RateLimiter,
+		) -> Result<JsonString, RuntimeError> {
+			match self.request(client, limiter, &self.to_url()).await {
+				// A set/collector pair that 404s is retried through the fuzzy
+				// named endpoint, the same fallback `parse_from` makes for
+				// ambiguous lines; a fuzzy name that 404s is simply a miss.
+				Err(RuntimeError::WebRequestNotFound) => {
+					match &self.resolution {
+						Resolution::SetCode { code, set } => {
+							let name = format!("{} {}", code, set);
+							self.request(
+								client,
+								limiter,
+								&fuzzy_named_url(&name),
+							)
+							.await
+						}
+						Resolution::FuzzyName(_) => {
+							Err(RuntimeError::WebRequestNotFound)
+						}
 					}
-				},
+				}
+				other => other,
+			}
+		}
 
-				None => return Err(RuntimeError::MalformedLineError),
-			};
+		// Parse a single decklist line, tolerating the formats MTG Arena and
+		// MTGO export. Returns `Ok(None)` for lines that carry no card — blank
+		// lines, comments (`//`, `#`) and section headers (`Deck`,
+		// `Sideboard`, …).
+		fn parse_from(line_str: &str) -> Result<Option<LineCard>, RuntimeError> {
+			let trimmed = line_str.trim();
+			if trimmed.is_empty()
+				|| trimmed.starts_with("//")
+				|| trimmed.starts_with('#')
+			{
+				return Ok(None);
+			}
 
-			let _code = match token_group.next() {
-				Some(s) => s,
-				None => return Err(RuntimeError::MalformedLineError),
-			};
+			// MTGO marks sideboard entries with a `SB:` prefix.
+			let trimmed = trimmed
+				.strip_prefix("SB:")
+				.map(str::trim)
+				.unwrap_or(trimmed);
+
+			if matches!(
+				trimmed.to_ascii_lowercase().as_str(),
+				"deck" | "sideboard" | "commander" | "maybeboard"
+			) {
+				return Ok(None);
+			}
+
+			let mut tokens = trimmed.split_whitespace();
+			let count_token = tokens
+				.next()
+				.ok_or(RuntimeError::MalformedLineError)?;
+			// Accept an optional `x` suffix on the count (`4x`).
+			let count = count_token
+				.strip_suffix('x')
+				.or_else(|| count_token.strip_suffix('X'))
+				.unwrap_or(count_token)
+				.parse::<u8>()
+				.map_err(|_| RuntimeError::InvalidCardCountNumberError)?;
 
-			let _set = match token_group.next() {
-				Some(s) => s,
-				None => return Err(RuntimeError::MalformedLineError),
+			let rest: Vec<&str> = tokens.collect();
+			if rest.is_empty() {
+				return Err(RuntimeError::MalformedLineError);
+			}
+
+			// A parenthesized set code — `(2X2)` — followed by a collector
+			// number selects a specific printing; everything before it is the
+			// card name. A set code with no collector number is ignored and the
+			// name is resolved by fuzzy lookup.
+			if let Some(position) =
+				rest.iter().position(|token| token.starts_with('('))
+			{
+				let set = rest[position]
+					.trim_start_matches('(')
+					.trim_end_matches(')');
+				return Ok(Some(match rest.get(position + 1) {
+					Some(collector) => LineCard {
+						count,
+						resolution: Resolution::SetCode {
+							code: set.to_string(),
+							set: collector.to_string(),
+						},
+					},
+					None => LineCard {
+						count,
+						resolution: Resolution::FuzzyName(
+							rest[..position].join(" "),
+						),
+					},
+				}));
+			}
+
+			// Otherwise fall back to the bare `<set> <code>` form, or treat the
+			// remainder as a fuzzy card name.
+			let resolution = match rest.as_slice() {
+				[code, set]
+					if is_set_code(code) && is_collector_number(set) =>
+				{
+					Resolution::SetCode {
+						code: code.to_string(),
+						set: set.to_string(),
+					}
+				}
+				_ => Resolution::FuzzyName(rest.join(" ")),
 			};
 
-			Ok(LineCard {
-				count: _count,
-				code: _code.to_string(),
-				set: _set.to_string(),
-			})
+			Ok(Some(LineCard { count, resolution }))
+		}
+
+		fn identifier(&self) -> CardIdentifier {
+			match &self.resolution {
+				Resolution::SetCode { code, set } => {
+					CardIdentifier::SetCollector {
+						set: code.clone(),
+						collector_number: set.clone(),
+					}
+				}
+				Resolution::FuzzyName(name) => {
+					CardIdentifier::Name { name: name.clone() }
+				}
+			}
 		}
 
 		fn to_url(&self) -> String {
-			format!(
-				"https://api.scryfall.com/cards/{}/{}",
-				self.code, self.set
-			)
+			match &self.resolution {
+				Resolution::SetCode { code, set } => format!(
+					"https://api.scryfall.com/cards/{}/{}",
+					code, set
+				),
+				Resolution::FuzzyName(name) => fuzzy_named_url(name),
+			}
 		}
 	}
 
 	impl HtmlImgContent for CardFace {
-		fn img_content(&self) -> String {
-			format!("<li><img src=\"{}\"></li>", self.image_uris.large)
+		fn img_content(&self, variant: ImageVariant) -> String {
+			format!(
+				"<li><img src=\"{}\"></li>",
+				self.image_uris.select(variant)
+			)
 		}
 	}
 
 	impl HtmlImgContent for MultiCardFace {
-		fn img_content(&self) -> String {
+		fn img_content(&self, variant: ImageVariant) -> String {
 			self.card_faces
 				.iter()
-				.map(|card_face| card_face.img_content())
+				.map(|card_face| card_face.img_content(variant))
 				.collect::<Vec<String>>()
 				.join("")
 		}
 	}
 
+	// The /cards/collection endpoint accepts up to 75 identifier objects per
+	// request.
+	const COLLECTION_CHUNK: usize = 75;
+
+	#[derive(serde::Serialize)]
+	#[serde(untagged)]
+	enum CardIdentifier {
+		SetCollector { set: String, collector_number: String },
+		Name { name: String },
+	}
+
+	#[derive(serde::Deserialize)]
+	struct CollectionResponse {
+		data: Vec<serde_json::Value>,
+	}
+
+	// POST the given cards to /cards/collection in chunks of 75, fetching the
+	// chunks concurrently. Returns every card the endpoint found; the caller
+	// matches them back to requests by identifier and handles any misses.
+	async fn fetch_collection(
+		client: &reqwest::Client,
+		limiter: &RateLimiter,
+		line_cards: &[&LineCard],
+	) -> Result<Vec<serde_json::Value>, RuntimeError> {
+		use futures::stream::StreamExt;
+
+		if line_cards.is_empty() {
+			return Ok(vec![]);
+		}
+
+		let chunks: Vec<&[&LineCard]> =
+			line_cards.chunks(COLLECTION_CHUNK).collect();
+
+		let mut responses: Vec<Option<CollectionResponse>> =
+			(0..chunks.len()).map(|_| None).collect();
+		let mut buffered = futures::stream::iter(
+			chunks.iter().enumerate().map(|(index, chunk)| {
+				let client = &client;
+				let limiter = &limiter;
+				async move {
+					limiter.throttle().await;
+					let identifiers: Vec<CardIdentifier> =
+						chunk.iter().map(LineCard::identifier).collect();
+					let body = serde_json::json!({ "identifiers": identifiers });
+					let result = async {
+						let response = client
+							.post("https://api.scryfall.com/cards/collection")
+							.header(reqwest::header::USER_AGENT, USER_AGENT)
+							.header(reqwest::header::ACCEPT, "application/json")
+							.json(&body)
+							.send()
+							.await
+							.map_err(|e| {
+								if e.is_timeout() {
+									RuntimeError::RequestTimeoutError
+								} else {
+									RuntimeError::WebRequestError
+								}
+							})?;
+
+						match response.status() {
+							reqwest::StatusCode::TOO_MANY_REQUESTS => {
+								return Err(RuntimeError::TooManyRequestsError)
+							}
+							status if !status.is_success() => {
+								return Err(RuntimeError::WebRequestError)
+							}
+							_ => {}
+						}
+
+						response
+							.json::<CollectionResponse>()
+							.await
+							.map_err(|_| RuntimeError::ParseJsonError)
+					}
+					.await;
+					(index, result)
+				}
+			}),
+		)
+		.buffer_unordered(MAX_IN_FLIGHT);
+
+		while let Some((index, result)) = buffered.next().await {
+			responses[index] = Some(result?);
+		}
+		drop(buffered);
+
+		// Collect every returned card; `not_found` identifiers are left for the
+		// caller to detect by failing to match them against the `data` set, so
+		// collapsed-duplicate and reordered responses are handled uniformly.
+		let mut cards: Vec<serde_json::Value> = vec![];
+		for response in responses.into_iter().map(|r| r.unwrap()) {
+			cards.extend(response.data);
+		}
+
+		Ok(cards)
+	}
+
+	// Canonical lookup key for an identifier we asked for.
+	fn identifier_key(identifier: &CardIdentifier) -> String {
+		match identifier {
+			CardIdentifier::SetCollector {
+				set,
+				collector_number,
+			} => format!(
+				"sc:{}|{}",
+				set.to_lowercase(),
+				collector_number.to_lowercase()
+			),
+			CardIdentifier::Name { name } => {
+				format!("n:{}", name.to_lowercase())
+			}
+		}
+	}
+
+	// Every key a returned card can satisfy: its set/collector pair and its
+	// name (plus the front-face name for double-faced cards, whose `name` is
+	// `Front // Back`).
+	fn card_value_keys(value: &serde_json::Value) -> Vec<String> {
+		let mut keys = vec![];
+		if let (Some(set), Some(collector)) = (
+			value.get("set").and_then(|v| v.as_str()),
+			value.get("collector_number").and_then(|v| v.as_str()),
+		) {
+			keys.push(format!(
+				"sc:{}|{}",
+				set.to_lowercase(),
+				collector.to_lowercase()
+			));
+		}
+		if let Some(name) = value.get("name").and_then(|v| v.as_str()) {
+			keys.push(format!("n:{}", name.to_lowercase()));
+			if let Some((front, _)) = name.split_once(" // ") {
+				keys.push(format!("n:{}", front.to_lowercase()));
+			}
+		}
+		keys
+	}
+
+	// Read-through wrapper over `fetch_collection`: cached cards skip the
+	// network entirely, only the misses are batched, and fresh responses are
+	// written back. Returns each card paired with its requested count, in
+	// input order.
+	async fn download_collection(
+		client: &reqwest::Client,
+		limiter: &RateLimiter,
+		line_cards: &[LineCard],
+		config: &Config,
+	) -> Result<Vec<(u8, serde_json::Value)>, RuntimeError> {
+		let mut values: Vec<Option<serde_json::Value>> =
+			Vec::with_capacity(line_cards.len());
+		let mut misses: Vec<usize> = vec![];
+
+		for (index, line_card) in line_cards.iter().enumerate() {
+			match cache::read(&line_card.to_url(), &config.cache) {
+				Some(json) => values.push(Some(
+					serde_json::from_str(&json)
+						.map_err(|_| RuntimeError::ParseJsonError)?,
+				)),
+				None => {
+					values.push(None);
+					misses.push(index);
+				}
+			}
+		}
+
+		let miss_cards: Vec<&LineCard> =
+			misses.iter().map(|&index| &line_cards[index]).collect();
+		let fetched =
+			fetch_collection(client, limiter, &miss_cards).await?;
+
+		// Index returned cards by identifier so duplicate or reordered
+		// responses map back to the right request; a single `data` entry can
+		// satisfy several identical identifiers.
+		let mut by_key: std::collections::HashMap<String, usize> =
+			std::collections::HashMap::new();
+		for (card_index, value) in fetched.iter().enumerate() {
+			for key in card_value_keys(value) {
+				by_key.insert(key, card_index);
+			}
+		}
+
+		let mut not_found: Vec<String> = vec![];
+		for &index in &misses {
+			let key = identifier_key(&line_cards[index].identifier());
+			let value = match by_key.get(&key) {
+				Some(&card_index) => Some(fetched[card_index].clone()),
+				// Cards the batch endpoint didn't return are retried one at a
+				// time through the single-card downloader, which also performs
+				// the set/collector 404 → fuzzy-name fallback.
+				None => match line_cards[index].download(client, limiter).await
+				{
+					Ok(json) => Some(parse_json::<serde_json::Value>(&json)?),
+					Err(_) => None,
+				},
+			};
+
+			match value {
+				Some(value) => {
+					cache::write(
+						&line_cards[index].to_url(),
+						&value.to_string(),
+						&config.cache,
+					)?;
+					values[index] = Some(value);
+				}
+				None => not_found.push(key),
+			}
+		}
+
+		if !not_found.is_empty() {
+			return Err(RuntimeError::CardsNotFoundError(not_found));
+		}
+
+		Ok(line_cards
+			.iter()
+			.map(|line_card| line_card.count)
+			.zip(
+				values
+					.into_iter()
+					.map(|value| value.unwrap_or(serde_json::Value::Null)),
+			)
+			.collect())
+	}
+
 	fn parse_json<T: for<'de> serde::Deserialize<'de> + Clone>(
 		data: &JsonString
 	) -> Result<T, RuntimeError> {
@@ -149,48 +821,131 @@ mod scryfall {
 		}
 	}
 
-	fn group_every_9(card_faces: &mut Vec<CardFace>) -> Vec<Vec<CardFace>> {
-		let mut counter = 0;
-		let mut outer_v: Vec<Vec<CardFace>> = vec![];
-		let mut inner_v: Vec<CardFace> = vec![];
+	// HTTP server mode: serve a form page and render submitted decklists.
+	pub mod server {
+		use super::{render_decklist, Config};
+		use std::sync::Arc;
+
+		const FORM_HTML: &'static str = indoc::indoc! {"
+			<!DOCTYPE html>
+			<html>
+			<head><title>Scryfall Proxy</title></head>
+			<body>
+				<form method=\"post\" action=\"/\">
+					<textarea name=\"decklist\" rows=\"20\" cols=\"60\"
+						placeholder=\"4 Lightning Bolt&#10;1 2x2 117\"></textarea>
+					<br>
+					<button type=\"submit\">Render proxy sheet</button>
+				</form>
+			</body>
+			</html>
+		"};
+
+		#[derive(serde::Deserialize)]
+		struct DeckForm {
+			decklist: String,
+		}
+
+		async fn form() -> axum::response::Html<&'static str> {
+			axum::response::Html(FORM_HTML)
+		}
 
-		for card_face in card_faces {
-			inner_v.push(card_face.clone());
+		async fn submit(
+			axum::extract::State(config): axum::extract::State<Arc<Config>>,
+			axum::extract::Form(form): axum::extract::Form<DeckForm>,
+		) -> axum::response::Response {
+			use axum::response::IntoResponse;
 
-			if counter % 9 == 0 {
-				outer_v.push(inner_v);
-				inner_v = vec![];
+			match render_decklist(&form.decklist, &config).await {
+				Ok(html) => axum::response::Html(html).into_response(),
+				Err(e) => (
+					axum::http::StatusCode::BAD_REQUEST,
+					crate::err_msg(e),
+				)
+					.into_response(),
 			}
+		}
 
-			counter += 1;
+		pub async fn serve(
+			config: Config,
+			addr: std::net::SocketAddr,
+		) -> std::io::Result<()> {
+			let app = axum::Router::new()
+				.route("/", axum::routing::get(form).post(submit))
+				.with_state(Arc::new(config));
+			let listener = tokio::net::TcpListener::bind(addr).await?;
+			axum::serve(listener, app).await
 		}
+	}
 
-		return outer_v;
+	// Split the flat card list into fixed-size pages.
+	fn paginate(card_faces: &[CardFace], page_size: usize) -> Vec<&[CardFace]> {
+		card_faces.chunks(page_size).collect()
 	}
 
-	pub fn exec() -> Result<String, RuntimeError> {
+	// Maximum number of in-flight requests; keeps the fetch stage fast without
+	// flooding Scryfall.
+	const MAX_IN_FLIGHT: usize = 10;
+
+	pub async fn exec(config: &Config) -> Result<String, RuntimeError> {
+		use std::io::Read;
+
+		let mut input = String::new();
+		std::io::stdin()
+			.read_to_string(&mut input)
+			.map_err(|_| RuntimeError::ParseStdinError)?;
+
+		render_decklist(&input, config).await
+	}
+
+	// Turn a raw decklist string into a printable HTML proxy sheet. Shared by
+	// the stdin CLI pipeline and the HTTP server handler.
+	pub async fn render_decklist(
+		input: &str,
+		config: &Config,
+	) -> Result<String, RuntimeError> {
 		let mut faces: Vec<CardFace> = vec![];
-		let mut html = String::from("");
 
-		for maybe_line in std::io::stdin().lines() {
-			let line_card = match maybe_line {
-				Ok(line) => LineCard::parse_from(&line)?,
-				Err(_) => return Err(RuntimeError::ParseStdinError),
-			};
+		let mut line_cards: Vec<LineCard> = vec![];
+		for (index, line) in input.lines().enumerate() {
+			match LineCard::parse_from(line) {
+				Ok(Some(line_card)) => line_cards.push(line_card),
+				Ok(None) => {}
+				Err(_) => {
+					return Err(RuntimeError::LineParseError(
+						index + 1,
+						line.to_string(),
+					))
+				}
+			}
+		}
 
-			let json_data = line_card.download()?;
+		let client = reqwest::Client::new();
+		let limiter = RateLimiter::new(std::time::Duration::from_millis(100));
 
-			if let Ok(card) = parse_json::<CardFace>(&json_data) {
+		let cards =
+			download_collection(&client, &limiter, &line_cards, config)
+				.await?;
+
+		for (count, value) in cards {
+			if let Ok(card) = serde_json::from_value::<CardFace>(value.clone()) {
 				let mut allocated_faces: Vec<CardFace> =
 					std::iter::repeat(card)
-						.take(line_card.count as usize)
+						.take(count as usize)
 						.collect();
 				faces.append(&mut allocated_faces);
-			} else if let Ok(card) = parse_json::<MultiCardFace>(&json_data) {
+			} else if let Ok(card) =
+				serde_json::from_value::<MultiCardFace>(value)
+			{
 				let mut allocated_multi_cardfaces: Vec<CardFace> =
 					std::iter::repeat(card)
-						.take(line_card.count as usize)
-						.map(|multi_cardface| multi_cardface.card_faces)
+						.take(count as usize)
+						.map(|mut multi_cardface| {
+							if config.front_only {
+								multi_cardface.card_faces.truncate(1);
+							}
+							multi_cardface.card_faces
+						})
 						.flatten()
 						.collect();
 				faces.append(&mut allocated_multi_cardfaces);
@@ -199,27 +954,23 @@ mod scryfall {
 			}
 		}
 
-		let body_html = group_every_9(&mut faces)
+		let body_html = paginate(&faces, config.grid.page_size())
 			.iter()
 			.map(|card_faces| {
 				card_faces
 					.iter()
-					.map(|card_face| card_face.img_content())
+					.map(|card_face| card_face.img_content(config.variant))
 					.collect::<Vec<String>>()
 					.join("")
 			})
 			.collect::<Vec<String>>()
 			.join("</ul><ul>");
 
-		html.push_str(BEGIN_DOCUMENT_HTML);
-		html.push_str(&body_html);
-		html.push_str(END_DOCUMENT_HTML);
-
-		Ok(html)
+		Ok(document(&config.grid, &body_html))
 	}
 }
 
-fn err_msg(e: scryfall::RuntimeError) -> &'static str {
+fn err_msg(e: scryfall::RuntimeError) -> String {
 	use scryfall::RuntimeError;
 	match e {
 		RuntimeError::InvalidCardCountNumberError => indoc::indoc! {"
@@ -230,7 +981,8 @@ fn err_msg(e: scryfall::RuntimeError) -> &'static str {
 				• <z> is the scryfall card code.
 
 			<x> failed to parse as a positive integer less than 256.
-		"},
+		"}
+		.to_string(),
 		RuntimeError::MalformedLineError => indoc::indoc! {"
 
 				Input parse failed.
@@ -238,20 +990,142 @@ fn err_msg(e: scryfall::RuntimeError) -> &'static str {
 					• <x> is the card count in the deck.
 					• <y> is the scryfall card set.
 					• <z> is the scryfall card code.
-		"},
+		"}
+		.to_string(),
 		RuntimeError::ParseJsonError =>
-			"JSON response parsing failed. However, the actual web request succeeded.",
+			"JSON response parsing failed. However, the actual web request succeeded."
+				.to_string(),
 		RuntimeError::ParseStdinError =>
-			"STDIN failed to parse.",
+			"STDIN failed to parse.".to_string(),
+		RuntimeError::CacheIoError =>
+			"Reading or writing the on-disk card cache failed.".to_string(),
+		RuntimeError::CardsNotFoundError(identifiers) => format!(
+			"Scryfall could not find the following cards: {}",
+			identifiers.join(", ")
+		),
+		RuntimeError::LineParseError(line, content) => format!(
+			"Failed to parse decklist line {}: \"{}\"",
+			line, content
+		),
+		RuntimeError::RequestTimeoutError =>
+			"Card web request timed out.".to_string(),
+		RuntimeError::TooManyRequestsError =>
+			"Scryfall returned 429 Too Many Requests; slow down and retry."
+				.to_string(),
 		RuntimeError::WebRequestBodyParseError =>
-			"Card web request downloaded successfully, but the body was malformed.",
+			"Card web request downloaded successfully, but the body was malformed."
+				.to_string(),
 		RuntimeError::WebRequestError =>
-			"Card web request failed.",
+			"Card web request failed.".to_string(),
+		RuntimeError::WebRequestNotFound =>
+			"Card web request returned 404 Not Found.".to_string(),
 	}
 }
 
-fn main() {
-	match scryfall::exec() {
+struct Cli {
+	config: scryfall::Config,
+	server: bool,
+	port: u16,
+}
+
+fn parse_config() -> Result<Cli, String> {
+	use scryfall::{Config, ImageVariant, Paper};
+
+	let mut config = Config::default();
+	let mut server = false;
+	let mut port = 8080u16;
+	let mut args = std::env::args().skip(1).peekable();
+	if args.peek().map(|a| a == "server").unwrap_or(false) {
+		server = true;
+		args.next();
+	}
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--port" => {
+				let value = args.next().ok_or_else(|| {
+					"--port requires a port number".to_string()
+				})?;
+				port = value.parse::<u16>().map_err(|_| {
+					format!("invalid --port value \"{}\"", value)
+				})?;
+			}
+			"--image" => {
+				let value = args.next().ok_or_else(|| {
+					"--image requires a variant".to_string()
+				})?;
+				config.variant =
+					ImageVariant::parse_from(&value).ok_or_else(|| {
+						format!("unknown image variant \"{}\"", value)
+					})?;
+			}
+			"--front-only" => config.front_only = true,
+			"--cards-per-row" => {
+				let value = args.next().ok_or_else(|| {
+					"--cards-per-row requires a number".to_string()
+				})?;
+				config.grid.cards_per_row = value.parse::<usize>().map_err(
+					|_| format!("invalid --cards-per-row value \"{}\"", value),
+				)?;
+			}
+			"--rows-per-page" => {
+				let value = args.next().ok_or_else(|| {
+					"--rows-per-page requires a number".to_string()
+				})?;
+				config.grid.rows_per_page = value.parse::<usize>().map_err(
+					|_| format!("invalid --rows-per-page value \"{}\"", value),
+				)?;
+			}
+			"--paper" => {
+				let value = args.next().ok_or_else(|| {
+					"--paper requires a size".to_string()
+				})?;
+				config.grid.paper =
+					Paper::parse_from(&value).ok_or_else(|| {
+						format!("unknown paper size \"{}\"", value)
+					})?;
+			}
+			"--no-cache" => config.cache.enabled = false,
+			"--refresh" => config.cache.refresh = true,
+			"--cache-ttl" => {
+				let value = args.next().ok_or_else(|| {
+					"--cache-ttl requires a duration in seconds".to_string()
+				})?;
+				let secs = value.parse::<u64>().map_err(|_| {
+					format!("invalid --cache-ttl value \"{}\"", value)
+				})?;
+				config.cache.ttl = std::time::Duration::from_secs(secs);
+			}
+			other => return Err(format!("unrecognized argument \"{}\"", other)),
+		}
+	}
+
+	Ok(Cli {
+		config,
+		server,
+		port,
+	})
+}
+
+#[tokio::main]
+async fn main() {
+	let cli = match parse_config() {
+		Ok(cli) => cli,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(1);
+		}
+	};
+
+	if cli.server {
+		let addr = std::net::SocketAddr::from(([127, 0, 0, 1], cli.port));
+		if let Err(e) = scryfall::server::serve(cli.config, addr).await {
+			eprintln!("Server error: {}", e);
+			std::process::exit(1);
+		}
+		return;
+	}
+
+	match scryfall::exec(&cli.config).await {
 		Ok(s) => println!("{}", s),
 		Err(e) => {
 			eprintln!("{}", err_msg(e));